@@ -2,8 +2,17 @@ use octocrab::{
     models::repos::{CommitAuthor, Object, Ref},
     params::repos::Reference,
 };
+use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
+/// Default page size used by [`GitHubClient::list_issues`] and
+/// [`GitHubClient::list_pull_requests`] when the caller doesn't specify one.
+const DEFAULT_PER_PAGE: u8 = 30;
+/// Maximum number of pages walked by a single listing call, so a
+/// caller-controlled `per_page`/filter combination can't trigger unbounded
+/// requests against the API rate limit.
+const MAX_LIST_PAGES: u32 = 10;
+
 /// `GitHubClient` is a client for interacting with one GitHub repository
 pub struct GitHubClient {
     pub octocrab: octocrab::Octocrab,
@@ -86,6 +95,7 @@ impl GitHubClient {
         &self,
         branch_name: &str,
         change: Change,
+        author: CommitAuthor,
     ) -> Result<(), GitHubClientError> {
         let repo_handler = self.octocrab.repos(&self.owner, &self.repo);
 
@@ -118,11 +128,7 @@ impl GitHubClient {
         // Commit the file to the specified branch
         new_file
             .branch(branch_name)
-            .author(CommitAuthor {
-                name: super::GITHUB_APP_NAME.into(),
-                email: super::GITHUB_APP_EMAIL.into(),
-                date: None,
-            })
+            .author(author)
             .send()
             .await
             .map_err(GitHubClientError::CreateCommit)?;
@@ -130,6 +136,110 @@ impl GitHubClient {
         Ok(())
     }
 
+    /// Commits a set of changes to a branch in a single atomic commit, built
+    /// through the low-level Git Data API (blobs/trees/commits) instead of
+    /// the Contents API. Unlike [`GitHubClient::add_change`], which creates one
+    /// commit per file, this lands the whole `changes` set in one commit so a
+    /// mid-batch failure can't leave the branch half-updated.
+    pub async fn commit_changes(
+        &self,
+        branch_name: &str,
+        message: &str,
+        changes: Vec<Change>,
+        author: CommitAuthor,
+    ) -> Result<(), GitHubClientError> {
+        let repo_handler = self.octocrab.repos(&self.owner, &self.repo);
+
+        // Resolve the branch ref to its tip commit SHA
+        let branch_ref = repo_handler
+            .get_ref(&Reference::Branch(branch_name.to_string()))
+            .await
+            .map_err(GitHubClientError::GetRef)?;
+        let tip_sha = match branch_ref.object {
+            Object::Commit { sha, .. } => sha,
+            _ => return Err(GitHubClientError::NoCommitInDefaultBranch),
+        };
+
+        // Fetch the tip commit to get its root tree SHA
+        let tip_commit: GitCommit = self
+            .octocrab
+            .get(
+                format!("/repos/{}/{}/git/commits/{}", self.owner, self.repo, tip_sha),
+                None::<&()>,
+            )
+            .await
+            .map_err(GitHubClientError::GetCommit)?;
+
+        // Create one blob per file, capturing its SHA
+        let mut tree_entries = Vec::with_capacity(changes.len());
+        for change in changes {
+            let blob: GitBlob = self
+                .octocrab
+                .post(
+                    format!("/repos/{}/{}/git/blobs", self.owner, self.repo),
+                    Some(&CreateBlobBody {
+                        content: change.content,
+                        encoding: "utf-8".into(),
+                    }),
+                )
+                .await
+                .map_err(GitHubClientError::CreateBlob)?;
+
+            tree_entries.push(GitTreeEntry {
+                path: change.path,
+                mode: "100644".into(),
+                r#type: "blob".into(),
+                sha: blob.sha,
+            });
+        }
+
+        // Create a new tree on top of the tip's tree
+        let tree: GitTree = self
+            .octocrab
+            .post(
+                format!("/repos/{}/{}/git/trees", self.owner, self.repo),
+                Some(&CreateTreeBody {
+                    base_tree: tip_commit.tree.sha,
+                    tree: tree_entries,
+                }),
+            )
+            .await
+            .map_err(GitHubClientError::CreateTree)?;
+
+        // Create the new commit, parented on the branch's previous tip
+        let commit: GitCommit = self
+            .octocrab
+            .post(
+                format!("/repos/{}/{}/git/commits", self.owner, self.repo),
+                Some(&CreateCommitBody {
+                    message: message.to_string(),
+                    tree: tree.sha,
+                    parents: vec![tip_sha],
+                    author,
+                }),
+            )
+            .await
+            .map_err(GitHubClientError::CreateCommit)?;
+
+        // Point the branch ref at the new commit
+        let _: Ref = self
+            .octocrab
+            .patch(
+                format!(
+                    "/repos/{}/{}/git/refs/heads/{}",
+                    self.owner, self.repo, branch_name
+                ),
+                Some(&UpdateRefBody {
+                    sha: commit.sha,
+                    force: false,
+                }),
+            )
+            .await
+            .map_err(GitHubClientError::UpdateRef)?;
+
+        Ok(())
+    }
+
     /// Creates a pull request in the repository.
     pub async fn create_pull_request(
         &self,
@@ -147,6 +257,153 @@ impl GitHubClient {
             .await
             .map_err(GitHubClientError::CreatePullRequest)
     }
+
+    /// Lists every open pull request in the repository, walking up to
+    /// [`MAX_LIST_PAGES`] pages.
+    pub async fn list_open_pull_requests(
+        &self,
+    ) -> Result<Vec<octocrab::models::pulls::PullRequest>, GitHubClientError> {
+        let page = self
+            .octocrab
+            .pulls(&self.owner, &self.repo)
+            .list()
+            .state(octocrab::params::State::Open)
+            .per_page(100)
+            .send()
+            .await
+            .map_err(GitHubClientError::ListPullRequests)?;
+
+        self.collect_pages(page)
+            .await
+            .map_err(GitHubClientError::ListPullRequests)
+    }
+
+    /// Lists issues in the repository matching `filter`, walking up to
+    /// [`MAX_LIST_PAGES`] pages. GitHub's issues listing endpoint returns
+    /// pull requests too (they share the same underlying resource), so
+    /// those are filtered out here to keep this method's result "real"
+    /// issues only.
+    pub async fn list_issues(
+        &self,
+        filter: &ListFilter,
+    ) -> Result<Vec<octocrab::models::issues::Issue>, GitHubClientError> {
+        let mut builder = self
+            .octocrab
+            .issues(&self.owner, &self.repo)
+            .list()
+            .state(filter.state.unwrap_or(octocrab::params::State::Open))
+            .per_page(filter.per_page.unwrap_or(DEFAULT_PER_PAGE));
+
+        if let Some(creator) = &filter.creator {
+            builder = builder.creator(creator);
+        }
+        if !filter.labels.is_empty() {
+            builder = builder.labels(&filter.labels);
+        }
+
+        let page = builder.send().await.map_err(GitHubClientError::ListIssues)?;
+        let issues = self
+            .collect_pages(page)
+            .await
+            .map_err(GitHubClientError::ListIssues)?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .collect())
+    }
+
+    /// Lists pull requests in the repository matching `filter`, walking up
+    /// to [`MAX_LIST_PAGES`] pages. Unlike [`GitHubClient::list_issues`],
+    /// GitHub's pull request listing endpoint has no `creator`/`labels`
+    /// query params, so those two filters are applied client-side instead.
+    pub async fn list_pull_requests(
+        &self,
+        filter: &ListFilter,
+    ) -> Result<Vec<octocrab::models::pulls::PullRequest>, GitHubClientError> {
+        let page = self
+            .octocrab
+            .pulls(&self.owner, &self.repo)
+            .list()
+            .state(filter.state.unwrap_or(octocrab::params::State::Open))
+            .per_page(filter.per_page.unwrap_or(DEFAULT_PER_PAGE))
+            .send()
+            .await
+            .map_err(GitHubClientError::ListPullRequests)?;
+
+        let mut pull_requests = self
+            .collect_pages(page)
+            .await
+            .map_err(GitHubClientError::ListPullRequests)?;
+
+        if let Some(creator) = &filter.creator {
+            pull_requests
+                .retain(|pr| pr.user.as_ref().is_some_and(|user| &user.login == creator));
+        }
+        if !filter.labels.is_empty() {
+            pull_requests.retain(|pr| {
+                pr.labels.as_ref().is_some_and(|labels| {
+                    filter
+                        .labels
+                        .iter()
+                        .all(|wanted| labels.iter().any(|label| &label.name == wanted))
+                })
+            });
+        }
+
+        Ok(pull_requests)
+    }
+
+    /// Walks `page` and subsequent pages via octocrab's pagination links,
+    /// stopping after [`MAX_LIST_PAGES`] pages so a caller-controlled
+    /// `per_page`/filter combination can't trigger unbounded requests
+    /// against the API rate limit.
+    async fn collect_pages<T: serde::de::DeserializeOwned>(
+        &self,
+        mut page: octocrab::Page<T>,
+    ) -> octocrab::Result<Vec<T>> {
+        let mut items = page.take_items();
+        let mut pages_fetched = 1;
+
+        while pages_fetched < MAX_LIST_PAGES {
+            let Some(mut next_page) = self.octocrab.get_page(&page.next).await? else {
+                break;
+            };
+            items.extend(next_page.take_items());
+            page = next_page;
+            pages_fetched += 1;
+        }
+
+        Ok(items)
+    }
+
+    /// Finds an open pull request authored by the bot to reuse instead of
+    /// opening a duplicate on a retry/re-delivery. When `branch_name` (a
+    /// stable, caller-derived key from the change set id) is given, it's
+    /// the sole match criterion; a title match alone isn't enough to claim a
+    /// branch-keyed PR, since two distinct change sets can share a title.
+    /// `branch_name` is only `None` for callers with no change set id to key
+    /// on, who fall back to matching by `title`.
+    pub fn find_bot_pull_request<'a>(
+        pull_requests: &'a [octocrab::models::pulls::PullRequest],
+        branch_name: Option<&str>,
+        title: &str,
+    ) -> Option<&'a octocrab::models::pulls::PullRequest> {
+        pull_requests.iter().find(|pr| {
+            let is_bot = pr
+                .user
+                .as_ref()
+                .is_some_and(|user| user.login.ends_with("[bot]"));
+            if !is_bot {
+                return false;
+            }
+
+            match branch_name {
+                Some(branch_name) => pr.head.ref_field == branch_name,
+                None => pr.title.as_deref() == Some(title),
+            }
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -157,9 +414,27 @@ pub enum GitHubClientError {
     CreateBranch(octocrab::Error),
     CreateCommit(octocrab::Error),
     CreatePullRequest(octocrab::Error),
+    GetRef(octocrab::Error),
+    GetCommit(octocrab::Error),
+    ListPullRequests(octocrab::Error),
+    ListIssues(octocrab::Error),
+    CreateBlob(octocrab::Error),
+    CreateTree(octocrab::Error),
+    UpdateRef(octocrab::Error),
     Unknown,
 }
 
+/// Filters accepted by [`GitHubClient::list_issues`] and
+/// [`GitHubClient::list_pull_requests`], mirroring octocrab's listing
+/// builder options.
+#[derive(Debug, Default)]
+pub struct ListFilter {
+    pub state: Option<octocrab::params::State>,
+    pub creator: Option<String>,
+    pub labels: Vec<String>,
+    pub per_page: Option<u8>,
+}
+
 #[derive(Debug)]
 pub struct CreateBranchResult {
     pub default_branch_name: String,
@@ -175,3 +450,53 @@ pub struct Change {
     /// Content of the file.
     pub content: String,
 }
+
+#[derive(Debug, Serialize)]
+struct CreateBlobBody {
+    content: String,
+    encoding: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitBlob {
+    sha: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GitTreeEntry {
+    path: String,
+    mode: String,
+    r#type: String,
+    sha: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateTreeBody {
+    base_tree: String,
+    tree: Vec<GitTreeEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTree {
+    sha: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommitBody {
+    message: String,
+    tree: String,
+    parents: Vec<String>,
+    author: CommitAuthor,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitCommit {
+    sha: String,
+    tree: GitTree,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdateRefBody {
+    sha: String,
+    force: bool,
+}
@@ -0,0 +1,142 @@
+use octocrab::{models::InstallationId, Octocrab};
+use secrecy::{ExposeSecret, SecretString};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
+
+/// How long before an installation token's assumed expiry we proactively
+/// refresh it, so a request never races the real expiry.
+const TOKEN_REFRESH_BUFFER: Duration = Duration::from_secs(5 * 60);
+/// GitHub installation access tokens are valid for one hour.
+const TOKEN_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug)]
+pub enum GetOctocrabError {
+    InvalidJsonWebToken(jsonwebtoken::errors::Error),
+    /// The app is not installed on this repository (the installation lookup
+    /// came back 403/404), as opposed to a transient failure.
+    AppNotInstalled,
+    OctocrabError(octocrab::Error),
+}
+
+struct CachedInstallationClient {
+    octocrab: Octocrab,
+    expires_at: Instant,
+}
+
+/// Caches app- and installation-scoped `Octocrab` clients so handlers don't
+/// rebuild the JWT encoding key and re-resolve the installation on every
+/// request. Keyed by installation id, with a `(owner, repo) -> installation_id`
+/// mapping cached alongside it.
+#[derive(Clone, Default)]
+pub struct ClientPool {
+    app_client: Arc<RwLock<Option<Octocrab>>>,
+    installation_ids: Arc<RwLock<HashMap<(String, String), InstallationId>>>,
+    installation_clients: Arc<RwLock<HashMap<InstallationId, CachedInstallationClient>>>,
+}
+
+impl ClientPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an installation-scoped `Octocrab` client for `(owner, repo)`,
+    /// reusing a cached client unless it's near its token's expiry.
+    pub async fn get_client_for_repo(
+        &self,
+        github_app_id: u64,
+        github_app_private_key: &SecretString,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Octocrab, GetOctocrabError> {
+        let installation_id = self
+            .resolve_installation_id(github_app_id, github_app_private_key, owner, repo)
+            .await?;
+
+        if let Some(cached) = self.installation_clients.read().await.get(&installation_id) {
+            if cached.expires_at > Instant::now() + TOKEN_REFRESH_BUFFER {
+                return Ok(cached.octocrab.clone());
+            }
+        }
+
+        let app_client = self
+            .app_client(github_app_id, github_app_private_key)
+            .await?;
+        let installation_client = app_client
+            .installation(installation_id)
+            .map_err(GetOctocrabError::OctocrabError)?;
+
+        self.installation_clients.write().await.insert(
+            installation_id,
+            CachedInstallationClient {
+                octocrab: installation_client.clone(),
+                expires_at: Instant::now() + TOKEN_LIFETIME,
+            },
+        );
+
+        Ok(installation_client)
+    }
+
+    async fn resolve_installation_id(
+        &self,
+        github_app_id: u64,
+        github_app_private_key: &SecretString,
+        owner: &str,
+        repo: &str,
+    ) -> Result<InstallationId, GetOctocrabError> {
+        let key = (owner.to_string(), repo.to_string());
+        if let Some(installation_id) = self.installation_ids.read().await.get(&key) {
+            return Ok(*installation_id);
+        }
+
+        let app_client = self
+            .app_client(github_app_id, github_app_private_key)
+            .await?;
+        let installation = app_client
+            .apps()
+            .get_repository_installation(owner, repo)
+            .await
+            .map_err(classify_installation_error)?;
+
+        self.installation_ids
+            .write()
+            .await
+            .insert(key, installation.id);
+        Ok(installation.id)
+    }
+
+    async fn app_client(
+        &self,
+        github_app_id: u64,
+        github_app_private_key: &SecretString,
+    ) -> Result<Octocrab, GetOctocrabError> {
+        if let Some(app_client) = self.app_client.read().await.as_ref() {
+            return Ok(app_client.clone());
+        }
+
+        let private_key = jsonwebtoken::EncodingKey::from_rsa_pem(
+            github_app_private_key.expose_secret().as_bytes(),
+        )
+        .map_err(GetOctocrabError::InvalidJsonWebToken)?;
+        let app_client = Octocrab::builder()
+            .app(octocrab::models::AppId(github_app_id), private_key)
+            .build()
+            .map_err(GetOctocrabError::OctocrabError)?;
+
+        *self.app_client.write().await = Some(app_client.clone());
+        Ok(app_client)
+    }
+}
+
+fn classify_installation_error(error: octocrab::Error) -> GetOctocrabError {
+    if let octocrab::Error::GitHub { ref source, .. } = error {
+        if matches!(source.status_code.as_u16(), 403 | 404) {
+            return GetOctocrabError::AppNotInstalled;
+        }
+    }
+
+    GetOctocrabError::OctocrabError(error)
+}
@@ -0,0 +1,112 @@
+use octocrab::{models::repos::CommitAuthor, Octocrab};
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+/// Path, relative to the repository root, of the per-repository config file.
+pub const CONFIG_FILE_PATH: &str = ".prof-tournesol.toml";
+
+/// Per-repository settings read from `.prof-tournesol.toml` on the default
+/// branch. Any field left unset in the file falls back to the service's
+/// built-in defaults.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RepoConfig {
+    pub branch_prefix: String,
+    pub base_branch: Option<String>,
+    pub labels: Vec<String>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+}
+
+impl Default for RepoConfig {
+    fn default() -> Self {
+        Self {
+            branch_prefix: String::from("fix/prof-tournesol"),
+            base_branch: None,
+            labels: Vec::new(),
+            author_name: None,
+            author_email: None,
+        }
+    }
+}
+
+impl RepoConfig {
+    /// Builds the `CommitAuthor` to stamp on commits, honoring the config's
+    /// author overrides and falling back to the bot's own identity.
+    pub fn commit_author(&self) -> CommitAuthor {
+        CommitAuthor {
+            name: self
+                .author_name
+                .clone()
+                .unwrap_or_else(|| super::GITHUB_APP_NAME.into()),
+            email: self
+                .author_email
+                .clone()
+                .unwrap_or_else(|| super::GITHUB_APP_EMAIL.into()),
+            date: None,
+        }
+    }
+}
+
+/// Caches [`RepoConfig`] per `(owner, repo)` so it isn't fetched from the
+/// default branch on every request.
+#[derive(Debug, Clone, Default)]
+pub struct RepoConfigCache {
+    inner: Arc<RwLock<HashMap<(String, String), RepoConfig>>>,
+}
+
+impl RepoConfigCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached config for `(owner, repo)`, loading and caching it
+    /// from the repository's default branch the first time it's requested.
+    pub async fn get_or_load(&self, octocrab: &Octocrab, owner: &str, repo: &str) -> RepoConfig {
+        let key = (owner.to_string(), repo.to_string());
+        if let Some(config) = self.inner.read().await.get(&key) {
+            return config.clone();
+        }
+
+        self.reload(octocrab, owner, repo).await
+    }
+
+    /// Forces a re-read of `.prof-tournesol.toml` from the default branch,
+    /// so a running instance can pick up edited config without a restart.
+    pub async fn reload(&self, octocrab: &Octocrab, owner: &str, repo: &str) -> RepoConfig {
+        let config = fetch_repo_config(octocrab, owner, repo).await;
+        self.inner
+            .write()
+            .await
+            .insert((owner.to_string(), repo.to_string()), config.clone());
+        config
+    }
+}
+
+async fn fetch_repo_config(octocrab: &Octocrab, owner: &str, repo: &str) -> RepoConfig {
+    let content_result = octocrab
+        .repos(owner, repo)
+        .get_content()
+        .path(CONFIG_FILE_PATH)
+        .send()
+        .await;
+
+    let raw = match content_result {
+        Ok(mut content) => content.take_items().into_iter().next(),
+        Err(_) => {
+            info!(owner, repo, "no {} found, using defaults", CONFIG_FILE_PATH);
+            None
+        }
+    };
+
+    let Some(decoded) = raw.and_then(|item| item.decoded_content()) else {
+        return RepoConfig::default();
+    };
+
+    toml::from_str(&decoded).unwrap_or_else(|e| {
+        error!(owner, repo, "failed to parse {}: {:?}", CONFIG_FILE_PATH, e);
+        RepoConfig::default()
+    })
+}
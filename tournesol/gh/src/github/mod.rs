@@ -0,0 +1,10 @@
+mod client;
+mod client_pool;
+mod config;
+
+pub use client::{Change, CreateBranchResult, GitHubClient, GitHubClientError, ListFilter};
+pub use client_pool::{ClientPool, GetOctocrabError};
+pub use config::{RepoConfig, RepoConfigCache, CONFIG_FILE_PATH};
+
+pub const GITHUB_APP_NAME: &str = "Prof. Tournesol";
+pub const GITHUB_APP_EMAIL: &str = "1299312+prof-tournesol[bot]@users.noreply.github.com";
@@ -1,5 +1,6 @@
 use axum::Router;
 use axum_extra::routing::RouterExt;
+use secrecy::SecretString;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
@@ -9,7 +10,10 @@ mod routes;
 pub fn create_root_app(state: AppState) -> Router {
     Router::new()
         .typed_post(routes::create_issue::create_issue_handler)
+        .typed_get(routes::list_issues::list_issues_handler)
         .typed_post(routes::create_pull_request::create_pull_request_handler)
+        .typed_get(routes::list_pull_requests::list_pull_requests_handler)
+        .typed_post(routes::webhooks::webhooks_handler)
         .typed_get(routes::health::health_handler)
         .with_state(state)
         .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
@@ -18,7 +22,20 @@ pub fn create_root_app(state: AppState) -> Router {
 #[derive(Clone)]
 pub struct AppState {
     pub github_app_id: u64,
-    pub github_app_private_key: String,
+    pub github_app_private_key: SecretString,
+    pub github_webhook_secret: SecretString,
+    pub repo_config_cache: github::RepoConfigCache,
+    pub client_pool: github::ClientPool,
+}
+
+impl std::fmt::Debug for AppState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppState")
+            .field("github_app_id", &self.github_app_id)
+            .field("github_app_private_key", &"[redacted]")
+            .field("github_webhook_secret", &"[redacted]")
+            .finish_non_exhaustive()
+    }
 }
 
 impl AppState {
@@ -27,12 +44,21 @@ impl AppState {
             .map_err(|_| String::from("GITHUB_APP_ID must be set"))?
             .parse::<u64>()
             .map_err(|_| String::from("GITHUB_APP_ID must be a valid u64"))?;
-        let github_app_private_key = std::env::var("GITHUB_APP_PRIVATE_KEY")
-            .map_err(|_| String::from("GITHUB_APP_PRIVATE_KEY must be set"))?;
+        let github_app_private_key = SecretString::from(
+            std::env::var("GITHUB_APP_PRIVATE_KEY")
+                .map_err(|_| String::from("GITHUB_APP_PRIVATE_KEY must be set"))?,
+        );
+        let github_webhook_secret = SecretString::from(
+            std::env::var("GITHUB_WEBHOOK_SECRET")
+                .map_err(|_| String::from("GITHUB_WEBHOOK_SECRET must be set"))?,
+        );
 
         Ok(Self {
             github_app_id,
             github_app_private_key,
+            github_webhook_secret,
+            repo_config_cache: github::RepoConfigCache::new(),
+            client_pool: github::ClientPool::new(),
         })
     }
 }
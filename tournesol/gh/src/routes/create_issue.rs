@@ -20,17 +20,19 @@ pub async fn create_issue_handler(
         req.title, req.owner, req.repo
     );
 
-    let octocrab = crate::github::get_octocrab_client_for_repo(
-        state.github_app_id,
-        &state.github_app_private_key,
-        &req.owner,
-        &req.repo,
-    )
-    .await
-    .map_err(|e| {
-        error!("failed to get octocrab client: {:?}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let octocrab = state
+        .client_pool
+        .get_client_for_repo(
+            state.github_app_id,
+            &state.github_app_private_key,
+            &req.owner,
+            &req.repo,
+        )
+        .await
+        .map_err(|e| {
+            error!("failed to get octocrab client: {:?}", e);
+            super::map_client_pool_error(e)
+        })?;
 
     let gh_client = GitHubClient::new(octocrab, req.owner, req.repo);
 
@@ -19,6 +19,10 @@ pub struct PullRequest {
     title: String,
     body: String,
     files: Vec<File>,
+    /// Stable identifier for the change this PR represents. When provided,
+    /// it's encoded into the branch name so retries land on the same branch
+    /// and pull request instead of opening a duplicate.
+    change_set_id: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -38,22 +42,81 @@ pub async fn create_pull_request_handler(
     let owner = req.owner;
     let repo_name = req.repo;
 
-    let octocrab = crate::github::get_octocrab_client_for_repo(
-        state.github_app_id,
-        &state.github_app_private_key,
-        &owner,
-        &repo_name,
-    )
-    .await
-    .map_err(|e| {
-        error!("failed to get octocrab client: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let octocrab = state
+        .client_pool
+        .get_client_for_repo(
+            state.github_app_id,
+            &state.github_app_private_key,
+            &owner,
+            &repo_name,
+        )
+        .await
+        .map_err(|e| {
+            error!("failed to get octocrab client: {:?}", e);
+            super::map_client_pool_error(e)
+        })?;
+
+    let repo_config = state
+        .repo_config_cache
+        .get_or_load(&octocrab, &owner, &repo_name)
+        .await;
 
     let gh_client = GitHubClient::new(octocrab, owner, repo_name);
 
+    // Commit all files in a single atomic commit via the Git Data API
+    let changes: Vec<Change> = req
+        .pr
+        .files
+        .into_iter()
+        .map(|file| Change {
+            path: file.path,
+            content: file.content,
+        })
+        .collect();
+    let commit_message = format!("fix: {}", req.pr.title);
+
+    // Dedup: reuse an existing open bot PR for this change set instead of
+    // opening a duplicate on every retry/re-delivery
+    let stable_branch_name = req
+        .pr
+        .change_set_id
+        .as_ref()
+        .map(|change_set_id| format!("{}/{}", repo_config.branch_prefix, change_set_id));
+
+    if let Some(branch_name) = &stable_branch_name {
+        let open_pull_requests = gh_client.list_open_pull_requests().await.map_err(|e| {
+            error!("list open pull requests error: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        if let Some(existing) = GitHubClient::find_bot_pull_request(
+            &open_pull_requests,
+            Some(branch_name),
+            &req.pr.title,
+        ) {
+            gh_client
+                .commit_changes(
+                    &existing.head.ref_field,
+                    &commit_message,
+                    changes,
+                    repo_config.commit_author(),
+                )
+                .await
+                .map_err(|e| {
+                    error!("create changes error: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+
+            info!(pr = existing.number, "reused existing pull request");
+            return Ok(format!("pull request #{} updated", existing.number));
+        }
+    }
+
+    let new_branch_name = stable_branch_name.unwrap_or_else(|| {
+        format!("{}/{}", repo_config.branch_prefix, uuid::Uuid::now_v7())
+    });
     let create_branch_result = gh_client
-        .create_branch(format!("fix/prof-tournesol/{}", uuid::Uuid::now_v7()))
+        .create_branch(new_branch_name)
         .await
         .map_err(|e| {
             error!("create branch error: {:?}", e);
@@ -61,30 +124,30 @@ pub async fn create_pull_request_handler(
         })?;
     info!(new_branch = ?create_branch_result.new_branch.url.path(), "branch created");
 
-    // Create changes
-    for file in req.pr.files {
-        gh_client
-            .add_change(
-                &create_branch_result.new_branch_name,
-                Change {
-                    path: file.path,
-                    content: file.content,
-                },
-            )
-            .await
-            .map_err(|e| {
-                error!("create changes error: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
-    }
+    gh_client
+        .commit_changes(
+            &create_branch_result.new_branch_name,
+            &commit_message,
+            changes,
+            repo_config.commit_author(),
+        )
+        .await
+        .map_err(|e| {
+            error!("create changes error: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
     info!(new_branch = ?create_branch_result.new_branch_name, "changes applied");
 
-    // Create pull request
+    // Create pull request, against the configured base branch or the
+    // repository's actual default branch if none is configured
+    let base_branch = repo_config
+        .base_branch
+        .unwrap_or(create_branch_result.default_branch_name);
     let pr_created = gh_client
         .create_pull_request(
             &req.pr.title,
             &req.pr.body,
-            "main",
+            &base_branch,
             &create_branch_result.new_branch_name,
         )
         .await
@@ -93,6 +156,17 @@ pub async fn create_pull_request_handler(
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
+    if !repo_config.labels.is_empty() {
+        if let Err(e) = gh_client
+            .octocrab
+            .issues(&gh_client.owner, &gh_client.repo)
+            .add_labels(pr_created.number, &repo_config.labels)
+            .await
+        {
+            error!("failed to apply default labels: {:?}", e);
+        }
+    }
+
     info!(pr_created = ?pr_created.url, "pull request created");
 
     Ok("pull request created".into())
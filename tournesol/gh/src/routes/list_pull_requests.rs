@@ -0,0 +1,56 @@
+use crate::{
+    github::{GitHubClient, ListFilter},
+    AppState,
+};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::error;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ListPullRequestsQuery {
+    owner: String,
+    repo: String,
+    state: Option<String>,
+    creator: Option<String>,
+    labels: Option<String>,
+    per_page: Option<u8>,
+}
+
+pub async fn list_pull_requests_handler(
+    _: super::CreatePullRequestPath,
+    State(state): State<AppState>,
+    Query(query): Query<ListPullRequestsQuery>,
+) -> Result<Json<Vec<octocrab::models::pulls::PullRequest>>, StatusCode> {
+    let octocrab = state
+        .client_pool
+        .get_client_for_repo(
+            state.github_app_id,
+            &state.github_app_private_key,
+            &query.owner,
+            &query.repo,
+        )
+        .await
+        .map_err(|e| {
+            error!("failed to get octocrab client: {:?}", e);
+            super::map_client_pool_error(e)
+        })?;
+
+    let gh_client = GitHubClient::new(octocrab, query.owner, query.repo);
+
+    let filter = ListFilter {
+        state: super::parse_state(query.state.as_deref())?,
+        creator: query.creator,
+        labels: super::parse_labels(query.labels),
+        per_page: query.per_page,
+    };
+
+    let pull_requests = gh_client.list_pull_requests(&filter).await.map_err(|e| {
+        error!("list pull requests error: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(pull_requests))
+}
@@ -1,9 +1,13 @@
+use crate::github::GetOctocrabError;
 use axum_extra::routing::TypedPath;
 use serde::Deserialize;
 
 pub mod create_issue;
 pub mod create_pull_request;
 pub mod health;
+pub mod list_issues;
+pub mod list_pull_requests;
+pub mod webhooks;
 
 #[derive(TypedPath, Deserialize)]
 #[typed_path("/issues")]
@@ -16,3 +20,48 @@ pub struct CreatePullRequestPath;
 #[derive(TypedPath, Deserialize)]
 #[typed_path("/health")]
 pub struct HealthPath;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/webhooks")]
+pub struct WebhooksPath;
+
+/// Parses the `state` query param accepted by the listing endpoints
+/// (`open`/`closed`/`all`), defaulting to `open` when absent. Rejects an
+/// unrecognized value rather than silently falling back to the default, so
+/// a typo'd filter doesn't come back as a different, wider result set.
+fn parse_state(raw: Option<&str>) -> Result<Option<octocrab::params::State>, axum::http::StatusCode> {
+    match raw {
+        None => Ok(None),
+        Some("open") => Ok(Some(octocrab::params::State::Open)),
+        Some("closed") => Ok(Some(octocrab::params::State::Closed)),
+        Some("all") => Ok(Some(octocrab::params::State::All)),
+        Some(_) => Err(axum::http::StatusCode::BAD_REQUEST),
+    }
+}
+
+/// Splits the `labels` query param (a comma-separated list) into its parts,
+/// dropping empty entries.
+fn parse_labels(raw: Option<String>) -> Vec<String> {
+    raw.map(|labels| {
+        labels
+            .split(',')
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(String::from)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Maps a [`GetOctocrabError`] to the status it should surface to the
+/// caller: the app not being installed on the named repo is the caller's
+/// mistake (wrong owner/repo, or the app was never added there), not a
+/// server fault, so it's distinguished from the catch-all 500.
+fn map_client_pool_error(error: GetOctocrabError) -> axum::http::StatusCode {
+    match error {
+        GetOctocrabError::AppNotInstalled => axum::http::StatusCode::NOT_FOUND,
+        GetOctocrabError::InvalidJsonWebToken(_) | GetOctocrabError::OctocrabError(_) => {
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
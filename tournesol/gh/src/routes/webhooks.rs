@@ -0,0 +1,331 @@
+use crate::{
+    AppState,
+    github::{Change, GitHubClient},
+};
+use axum::{body::Bytes, extract::State, http::HeaderMap, http::StatusCode};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::Sha256;
+use tracing::{error, info, warn};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, serde::Deserialize)]
+struct IssueCommentEvent {
+    action: String,
+    comment: Comment,
+    issue: Issue,
+    repository: Repository,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Comment {
+    body: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Issue {
+    number: u64,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Repository {
+    name: String,
+    owner: RepositoryOwner,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RepositoryOwner {
+    login: String,
+}
+
+/// Slash command recognized in an `issue_comment` body, e.g. `/tournesol fix src/main.rs`.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Fix { path: String },
+}
+
+pub async fn webhooks_handler(
+    _: super::WebhooksPath,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, StatusCode> {
+    verify_signature(&state.github_webhook_secret, &headers, &body)?;
+
+    let event_type = headers
+        .get("X-GitHub-Event")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    match event_type {
+        "issue_comment" => handle_issue_comment(state, &body).await,
+        "pull_request" => handle_pull_request(&body).await,
+        "push" => handle_push(state, &body).await,
+        other => {
+            info!(event_type = other, "ignoring unhandled webhook event");
+            Ok(StatusCode::OK)
+        }
+    }
+}
+
+/// Verifies the `X-Hub-Signature-256` header by recomputing the HMAC-SHA256
+/// digest over the raw request body, so the MAC covers the exact bytes GitHub
+/// sent (and not a re-serialized copy of the parsed JSON).
+fn verify_signature(
+    secret: &SecretString,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(), StatusCode> {
+    let signature_header = headers
+        .get("X-Hub-Signature-256")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let expected_hex = signature_header
+        .strip_prefix("sha256=")
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let expected = hex::decode(expected_hex).map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let mut mac = HmacSha256::new_from_slice(secret.expose_secret().as_bytes()).map_err(|e| {
+        error!("invalid webhook secret: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    mac.update(body);
+    mac.verify_slice(&expected).map_err(|_| {
+        warn!("webhook signature mismatch");
+        StatusCode::UNAUTHORIZED
+    })
+}
+
+async fn handle_issue_comment(state: AppState, body: &[u8]) -> Result<StatusCode, StatusCode> {
+    let event: IssueCommentEvent =
+        serde_json::from_slice(body).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    if event.action != "created" {
+        return Ok(StatusCode::OK);
+    }
+
+    let Some(command) = parse_command(&event.comment.body) else {
+        return Ok(StatusCode::OK);
+    };
+
+    info!(
+        issue = event.issue.number,
+        repo = event.repository.name,
+        "dispatching slash command {:?}", command
+    );
+
+    let owner = event.repository.owner.login;
+    let repo = event.repository.name;
+
+    let octocrab = state
+        .client_pool
+        .get_client_for_repo(
+            state.github_app_id,
+            &state.github_app_private_key,
+            &owner,
+            &repo,
+        )
+        .await
+        .map_err(|e| {
+            error!("failed to get octocrab client: {:?}", e);
+            super::map_client_pool_error(e)
+        })?;
+
+    let repo_config = state
+        .repo_config_cache
+        .get_or_load(&octocrab, &owner, &repo)
+        .await;
+
+    let gh_client = GitHubClient::new(octocrab, owner, repo);
+
+    match command {
+        Command::Fix { path } => {
+            run_fix_command(&gh_client, &repo_config, event.issue.number, &path).await?
+        }
+    }
+
+    Ok(StatusCode::OK)
+}
+
+async fn handle_pull_request(body: &[u8]) -> Result<StatusCode, StatusCode> {
+    #[derive(Debug, serde::Deserialize)]
+    struct PullRequestEvent {
+        action: String,
+    }
+
+    let event: PullRequestEvent =
+        serde_json::from_slice(body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    info!(action = event.action, "received pull_request event");
+
+    Ok(StatusCode::OK)
+}
+
+/// Reloads the cached [`crate::github::RepoConfig`] when a push to the
+/// default branch touches `.prof-tournesol.toml`, so a running instance
+/// picks up edited config without a restart.
+async fn handle_push(state: AppState, body: &[u8]) -> Result<StatusCode, StatusCode> {
+    #[derive(Debug, serde::Deserialize)]
+    struct PushEvent {
+        r#ref: String,
+        repository: PushRepository,
+        commits: Vec<PushCommit>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct PushRepository {
+        name: String,
+        owner: RepositoryOwner,
+        default_branch: String,
+    }
+
+    #[derive(Debug, Default, serde::Deserialize)]
+    struct PushCommit {
+        #[serde(default)]
+        added: Vec<String>,
+        #[serde(default)]
+        modified: Vec<String>,
+        #[serde(default)]
+        removed: Vec<String>,
+    }
+
+    let event: PushEvent = serde_json::from_slice(body).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let default_branch_ref = format!("refs/heads/{}", event.repository.default_branch);
+    if event.r#ref != default_branch_ref {
+        return Ok(StatusCode::OK);
+    }
+
+    let touched_config = event.commits.iter().any(|commit| {
+        commit
+            .added
+            .iter()
+            .chain(&commit.modified)
+            .chain(&commit.removed)
+            .any(|path| path == crate::github::CONFIG_FILE_PATH)
+    });
+    if !touched_config {
+        return Ok(StatusCode::OK);
+    }
+
+    let owner = event.repository.owner.login;
+    let repo = event.repository.name;
+
+    let octocrab = state
+        .client_pool
+        .get_client_for_repo(
+            state.github_app_id,
+            &state.github_app_private_key,
+            &owner,
+            &repo,
+        )
+        .await
+        .map_err(|e| {
+            error!("failed to get octocrab client: {:?}", e);
+            super::map_client_pool_error(e)
+        })?;
+
+    state.repo_config_cache.reload(&octocrab, &owner, &repo).await;
+    info!(owner, repo, "reloaded repo config after push to default branch");
+
+    Ok(StatusCode::OK)
+}
+
+/// Parses a `/tournesol <command> <args>` slash command out of a comment body.
+/// Only the first matching line is honored.
+fn parse_command(comment_body: &str) -> Option<Command> {
+    for line in comment_body.lines() {
+        let Some(rest) = line.trim().strip_prefix("/tournesol ") else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+        match parts.next() {
+            Some("fix") => {
+                if let Some(path) = parts.next() {
+                    return Some(Command::Fix { path: path.to_string() });
+                }
+            }
+            _ => continue,
+        }
+    }
+    None
+}
+
+async fn run_fix_command(
+    gh_client: &GitHubClient,
+    repo_config: &crate::github::RepoConfig,
+    issue_number: u64,
+    path: &str,
+) -> Result<(), StatusCode> {
+    // There's no actual fix-generation logic yet, so the only change we can
+    // stage is an empty placeholder file. That's harmless for a path that
+    // doesn't exist yet, but would truncate a real file to zero bytes and
+    // open a PR to merge that deletion, so refuse to do it over an existing
+    // path until real fix content is available.
+    let path_exists = gh_client
+        .octocrab
+        .repos(&gh_client.owner, &gh_client.repo)
+        .get_content()
+        .path(path)
+        .send()
+        .await
+        .is_ok();
+    if path_exists {
+        info!(
+            path,
+            issue = issue_number,
+            "skipping /tournesol fix: path already exists and fix generation isn't implemented yet"
+        );
+        return Ok(());
+    }
+
+    let create_branch_result = gh_client
+        .create_branch(format!(
+            "{}/{}",
+            repo_config.branch_prefix,
+            uuid::Uuid::now_v7()
+        ))
+        .await
+        .map_err(|e| {
+            error!("create branch error: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // A single file is being staged, so use the per-file Contents API path
+    // rather than the batch Git Data API one `commit_changes` uses.
+    gh_client
+        .add_change(
+            &create_branch_result.new_branch_name,
+            Change {
+                path: path.to_string(),
+                content: String::new(),
+            },
+            repo_config.commit_author(),
+        )
+        .await
+        .map_err(|e| {
+            error!("create changes error: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let base_branch = repo_config
+        .base_branch
+        .clone()
+        .unwrap_or(create_branch_result.default_branch_name);
+    let pr_created = gh_client
+        .create_pull_request(
+            &format!("fix: {}", path),
+            &format!("Requested by /tournesol fix in #{}", issue_number),
+            &base_branch,
+            &create_branch_result.new_branch_name,
+        )
+        .await
+        .map_err(|e| {
+            error!("create pull request error: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    info!(pr_created = ?pr_created.url, "pull request created from comment command");
+
+    Ok(())
+}